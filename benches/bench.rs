@@ -0,0 +1,30 @@
+#![feature(test)]
+
+extern crate test;
+extern crate typed_arena;
+
+use test::Bencher;
+use typed_arena::Arena;
+
+struct Point {
+    x: i32,
+    y: i32,
+    z: i32,
+}
+
+#[bench]
+fn bench_alloc(b: &mut Bencher) {
+    let arena = Arena::with_capacity(1024);
+    b.iter(|| arena.alloc(Point { x: 1, y: 2, z: 3 }));
+}
+
+#[bench]
+fn bench_alloc_fresh_arena(b: &mut Bencher) {
+    b.iter(|| {
+        let arena = Arena::new();
+        for _ in 0..1024 {
+            arena.alloc(Point { x: 1, y: 2, z: 3 });
+        }
+        arena
+    });
+}
@@ -50,6 +50,13 @@
 //! a.other.set(Some(b));
 //! b.other.set(Some(a));
 //! ```
+//!
+//! By default, a `CycleParticipant` cannot also implement `Drop`, because
+//! dropping it might access the `other` reference after the arena itself
+//! has started tearing down, which `Arena`'s own destructor cannot prove is
+//! sound. Enabling the nightly-only `may_dangle` Cargo feature relaxes this:
+//! `Arena<T>`'s destructor is then marked `#[may_dangle]`, so `T` is allowed
+//! to implement `Drop` and still hold references into the same arena.
 
 // Potential optimizations:
 // 1) add and stabilize a method for in-place reallocation of vecs.
@@ -59,6 +66,7 @@
 #![deny(missing_docs)]
 #![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![cfg_attr(not(feature = "std"), feature(alloc))]
+#![cfg_attr(feature = "may_dangle", feature(dropck_eyepatch))]
 
 #[cfg(not(feature = "std"))]
 extern crate alloc;
@@ -69,11 +77,19 @@ extern crate core;
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
+use core::alloc::Layout;
+use core::cell::Cell;
 use core::cell::RefCell;
+use core::cell::UnsafeCell;
 use core::cmp;
 use core::iter;
 use core::mem;
+use core::ptr;
 use core::slice;
+use core::str;
+
+#[cfg(feature = "std")]
+use std::sync::Mutex;
 
 #[cfg(test)]
 mod test;
@@ -82,6 +98,8 @@ mod test;
 const INITIAL_SIZE: usize = 1024;
 // Minimum capacity. Must be larger than 0.
 const MIN_CAPACITY: usize = 1;
+// Number of `T`s an `Arena` stores inline, before it ever touches the heap.
+const INLINE_CAPACITY: usize = 8;
 
 /// An arena of objects of type `T`.
 ///
@@ -100,6 +118,37 @@ const MIN_CAPACITY: usize = 1;
 /// assert!(vegeta.level > 9000);
 /// ```
 pub struct Arena<T> {
+    // Bump-allocation cursor: the next free slot in `chunks.current`, and
+    // one past the last usable slot. `alloc`'s fast path only ever reads
+    // and bumps these two pointers, so it never needs to borrow `chunks`.
+    // They are meaningless (and left null) until the arena has spilled
+    // onto the heap — see `inline_used` below.
+    //
+    // `chunks.current`'s own length is therefore allowed to go stale
+    // (lagging behind `next`) between calls to `alloc`; every other method
+    // resynchronizes it (via `sync_current_len`) before it looks at or
+    // mutates `current` through the ordinary `Vec` API.
+    next: Cell<*mut T>,
+    end: Cell<*mut T>,
+    // How many values are stored in `inline`, up to `INLINE_CAPACITY`.
+    //
+    // `inline` is a permanent first chunk: once a value has been written to
+    // it, it is never moved or copied elsewhere, so every `&mut T`/`&T`
+    // `alloc` has ever handed out into `inline` stays valid for the
+    // `Arena`'s whole lifetime. Once the arena spills onto the heap (see
+    // `end` above), `inline_used` is frozen — later `alloc`s go through
+    // `next`/`end` instead, and every method that walks the arena's
+    // contents (`len`, `iter`, `into_vec`, `Drop`, ...) treats `inline`'s
+    // `inline_used` values as preceding whatever's in `chunks`.
+    //
+    // This has to be a plain count rather than a cached pointer: `Arena` is
+    // an ordinary movable value, and a pointer into `inline` computed on
+    // one call would dangle on the next if the arena had moved in between
+    // (heap-allocated chunks don't have this problem, since moving the
+    // `Arena` only moves the `Vec`'s `{ptr, len, cap}` fields, not the
+    // heap allocation `ptr` points to).
+    inline_used: Cell<usize>,
+    inline: UnsafeCell<[mem::MaybeUninit<T>; INLINE_CAPACITY]>,
     chunks: RefCell<ChunkList<T>>,
 }
 
@@ -111,6 +160,11 @@ struct ChunkList<T> {
 impl<T> Arena<T> {
     /// Construct a new arena.
     ///
+    /// The first handful of values are stored inline in the `Arena` itself,
+    /// so an arena that never allocates more than that is never backed by
+    /// the heap at all — handy for the many short-lived arenas a
+    /// recursive-descent parser creates for small expressions.
+    ///
     /// ## Example
     ///
     /// ```
@@ -120,12 +174,27 @@ impl<T> Arena<T> {
     /// # arena.alloc(1);
     /// ```
     pub fn new() -> Arena<T> {
-        let size = cmp::max(1, mem::size_of::<T>());
-        Arena::with_capacity(INITIAL_SIZE / size)
+        // Start out using only `inline`'s storage, so an arena that never
+        // allocates more than `INLINE_CAPACITY` values never touches the
+        // heap at all. `next`/`end` are unused (and left null) until the
+        // arena spills; see `inline_used`.
+        Arena {
+            next: Cell::new(ptr::null_mut()),
+            end: Cell::new(ptr::null_mut()),
+            inline_used: Cell::new(0),
+            inline: Arena::empty_inline(),
+            chunks: RefCell::new(ChunkList {
+                current: Vec::new(),
+                rest: Vec::new(),
+            }),
+        }
     }
 
     /// Construct a new arena with capacity for `n` values pre-allocated.
     ///
+    /// If `n` is small enough to fit inline (see the `new` docs), this is
+    /// the same as `Arena::new`.
+    ///
     /// ## Example
     ///
     /// ```
@@ -135,15 +204,71 @@ impl<T> Arena<T> {
     /// # arena.alloc(1);
     /// ```
     pub fn with_capacity(n: usize) -> Arena<T> {
-        let n = cmp::max(MIN_CAPACITY, n);
+        if n <= INLINE_CAPACITY {
+            return Arena::new();
+        }
+        let mut current = Vec::with_capacity(n);
+        let next: *mut T = current.as_mut_ptr();
+        // Safety: `current` was just allocated with capacity `n`, so one
+        // past its last slot is a valid (if not dereferenceable) pointer.
+        let end = unsafe { next.add(current.capacity()) };
         Arena {
+            next: Cell::new(next),
+            end: Cell::new(end),
+            // Inline storage is skipped entirely: `end` is non-null from
+            // the start, so nothing is ever written to `inline` and it
+            // holds no values.
+            inline_used: Cell::new(0),
+            inline: Arena::empty_inline(),
             chunks: RefCell::new(ChunkList {
-                current: Vec::with_capacity(n),
+                current,
                 rest: Vec::new(),
             }),
         }
     }
 
+    // An uninitialized inline buffer. Wrapping the whole array (rather than
+    // each element) in `MaybeUninit` before calling `assume_init` is sound
+    // regardless of `T`, because `MaybeUninit` itself carries no validity
+    // requirement.
+    fn empty_inline() -> UnsafeCell<[mem::MaybeUninit<T>; INLINE_CAPACITY]> {
+        UnsafeCell::new(unsafe {
+            mem::MaybeUninit::<[mem::MaybeUninit<T>; INLINE_CAPACITY]>::uninit().assume_init()
+        })
+    }
+
+    #[inline]
+    fn inline_ptr(&self) -> *mut T {
+        self.inline.get() as *mut T
+    }
+
+    // Gives the arena its first heap-backed chunk, the first time an
+    // allocation doesn't fit in `inline`. `inline`'s existing contents are
+    // *not* moved here — doing so would relocate values out from under
+    // references `alloc` already handed out to callers. Instead `inline`
+    // stays put as a permanent (frozen) first chunk, and `chunks.current`
+    // starts out empty and behaves exactly like any other heap-backed chunk
+    // from here on.
+    #[inline(never)]
+    #[cold]
+    fn spill_inline(&self, chunks: &mut ChunkList<T>) {
+        let new_capacity = cmp::max(MIN_CAPACITY, INLINE_CAPACITY * 2);
+        chunks.current = Vec::with_capacity(new_capacity);
+    }
+
+    // Dispatches to whichever of `spill_inline`/`sync_current_len` brings
+    // `chunks.current` up to date, depending on whether the arena has
+    // spilled onto the heap yet. Call this after borrowing `chunks` and
+    // before doing anything else with `current`.
+    #[inline]
+    fn prepare_current(&self, chunks: &mut ChunkList<T>) {
+        if chunks.current.capacity() == 0 {
+            self.spill_inline(chunks);
+        } else {
+            self.sync_current_len(chunks);
+        }
+    }
+
     /// Allocates a value in the arena, and returns a mutable reference
     /// to that value.
     ///
@@ -158,25 +283,66 @@ impl<T> Arena<T> {
     /// ```
     #[inline]
     pub fn alloc(&self, value: T) -> &mut T {
-        self.alloc_fast_path(value)
-            .unwrap_or_else(|value| self.alloc_slow_path(value))
-    }
-
-    #[inline]
-    fn alloc_fast_path(&self, value: T) -> Result<&mut T, T> {
-        let mut chunks = self.chunks.borrow_mut();
-        if chunks.current.len() < chunks.current.capacity() {
-            chunks.current.push(value);
-            Ok(unsafe { mem::transmute(chunks.current.last_mut().unwrap()) })
+        // `end` is null exactly until the arena spills onto the heap (see
+        // `new`), so this also guards against writing into `inline` again
+        // once allocation has moved on to `chunks` — even if `inline_used`
+        // is still short of `INLINE_CAPACITY` because an earlier
+        // `alloc_extend`/`alloc_uninitialized` call spilled early. Writing
+        // there instead would be out of allocation order relative to
+        // whatever's already in `chunks`.
+        if self.end.get().is_null() {
+            let used = self.inline_used.get();
+            if used < INLINE_CAPACITY {
+                self.inline_used.set(used + 1);
+                unsafe {
+                    let ptr = self.inline_ptr().add(used);
+                    ptr::write(ptr, value);
+                    return &mut *ptr;
+                }
+            }
+        }
+        let ptr = self.next.get();
+        if ptr == self.end.get() {
+            self.alloc_slow_path(value)
         } else {
-            Err(value)
+            unsafe {
+                self.next.set(ptr.add(1));
+                ptr::write(ptr, value);
+                &mut *ptr
+            }
         }
     }
 
+    #[inline(never)]
+    #[cold]
     fn alloc_slow_path(&self, value: T) -> &mut T {
         &mut self.alloc_extend(iter::once(value))[0]
     }
 
+    // `current`'s length is the source of truth everywhere except in
+    // `alloc`'s fast path, which advances `next` directly without touching
+    // it. Call this after borrowing `chunks` and before doing anything else
+    // with `current`, so it is never seen in a stale state.
+    #[inline]
+    fn sync_current_len(&self, chunks: &mut ChunkList<T>) {
+        if mem::size_of::<T>() != 0 {
+            let len =
+                (self.next.get() as usize - chunks.current.as_ptr() as usize) / mem::size_of::<T>();
+            unsafe { chunks.current.set_len(len) };
+        }
+    }
+
+    // The inverse of `sync_current_len`: bring the bump pointers back in
+    // line with `current` after a method has grown it or swapped it out.
+    #[inline]
+    fn sync_bump_pointers(&self, chunks: &ChunkList<T>) {
+        let ptr = chunks.current.as_ptr() as *mut T;
+        unsafe {
+            self.next.set(ptr.add(chunks.current.len()));
+            self.end.set(ptr.add(chunks.current.capacity()));
+        }
+    }
+
     /// Uses the contents of an iterator to allocate values in the arena.
     /// Returns a mutable slice that contains these values.
     ///
@@ -196,6 +362,7 @@ impl<T> Arena<T> {
         let mut iter = iterable.into_iter();
 
         let mut chunks = self.chunks.borrow_mut();
+        self.prepare_current(&mut chunks);
 
         let iter_min_len = iter.size_hint().0;
         let mut next_item_index;
@@ -229,6 +396,7 @@ impl<T> Arena<T> {
                 i += 1;
             }
         }
+        self.sync_bump_pointers(&chunks);
         let new_slice_ref = {
             let new_slice_ref = &mut chunks.current[next_item_index..];
 
@@ -267,7 +435,21 @@ impl<T> Arena<T> {
     /// consider all the places where your code might "unexpectedly" drop values
     /// earlier than it "should" because of unwinding during panics.
     pub unsafe fn alloc_uninitialized(&self, num: usize) -> *mut [T] {
+        // Mirror `uninitialized_array`'s inline case: if the whole request
+        // still fits in `inline`, it must be served from there rather than
+        // spilling, since a prior call to `uninitialized_array` may already
+        // have handed the caller this exact inline range to write into.
+        if self.end.get().is_null() {
+            let used = self.inline_used.get();
+            if num <= INLINE_CAPACITY - used {
+                self.inline_used.set(used + num);
+                let ptr = self.inline_ptr().add(used);
+                return slice::from_raw_parts_mut(ptr, num) as *mut _;
+            }
+        }
+
         let mut chunks = self.chunks.borrow_mut();
+        self.prepare_current(&mut chunks);
 
         if chunks.current.len() + num > chunks.current.capacity() {
             chunks.reserve(num);
@@ -276,6 +458,7 @@ impl<T> Arena<T> {
         // At this point, the current chunk must have free capacity.
         let next_item_index = chunks.current.len();
         chunks.current.set_len(next_item_index + num);
+        self.sync_bump_pointers(&chunks);
         // Extend the lifetime...
         &mut chunks.current[next_item_index..] as *mut _
     }
@@ -287,7 +470,16 @@ impl<T> Arena<T> {
     /// `alloc_uninitialized`, or `alloc_extend` which is why the method is
     /// safe.
     pub fn uninitialized_array(&self) -> *mut [T] {
-        let chunks = self.chunks.borrow();
+        let mut chunks = self.chunks.borrow_mut();
+        if chunks.current.capacity() == 0 {
+            // Still inline: report the unused tail of `inline` without
+            // forcing a spill onto the heap.
+            let used = self.inline_used.get();
+            let len = INLINE_CAPACITY - used;
+            let ptr = unsafe { self.inline_ptr().add(used) };
+            return unsafe { slice::from_raw_parts_mut(ptr, len) as *mut _ };
+        }
+        self.sync_current_len(&mut chunks);
         let len = chunks.current.capacity() - chunks.current.len();
         let next_item_index = chunks.current.len();
         let slice = &chunks.current[next_item_index..];
@@ -315,19 +507,258 @@ impl<T> Arena<T> {
     /// assert_eq!(easy_as_123, vec!["a", "b", "c"]);
     /// ```
     pub fn into_vec(self) -> Vec<T> {
-        let mut chunks = self.chunks.into_inner();
-        // keep order of allocation in the resulting Vec
-        let n = chunks
+        // `Arena` has a custom `Drop` impl (to sync `current`'s length
+        // before it's dropped), so we can't destructure `self` directly.
+        // Go through `ManuallyDrop`, sync the length, then swap the chunk
+        // list out for an empty one to get true ownership of it — `this`
+        // is never actually dropped, so anything left inside its `RefCell`
+        // would otherwise leak.
+        let this = mem::ManuallyDrop::new(self);
+
+        let still_inline = { this.chunks.borrow().current.capacity() == 0 };
+        if still_inline {
+            // Never spilled onto the heap: just copy `inline`'s contents
+            // out into a freshly allocated `Vec`.
+            let len = this.inline_used.get();
+            let mut result = Vec::with_capacity(len);
+            if len != 0 {
+                unsafe {
+                    ptr::copy_nonoverlapping(this.inline_ptr(), result.as_mut_ptr(), len);
+                    result.set_len(len);
+                }
+            }
+            return result;
+        }
+
+        {
+            let mut chunks = this.chunks.borrow_mut();
+            this.sync_current_len(&mut chunks);
+        }
+        let chunks = this.chunks.replace(ChunkList {
+            current: Vec::new(),
+            rest: Vec::new(),
+        });
+
+        // `inline` was frozen the moment the arena spilled (see
+        // `spill_inline`), so its values come first in allocation order,
+        // ahead of anything in `chunks`.
+        let inline_len = this.inline_used.get();
+        let heap_len = chunks
             .rest
             .iter()
             .fold(chunks.current.len(), |a, v| a + v.len());
-        let mut result = Vec::with_capacity(n);
+        let mut result = Vec::with_capacity(inline_len + heap_len);
+        if inline_len != 0 {
+            unsafe {
+                ptr::copy_nonoverlapping(this.inline_ptr(), result.as_mut_ptr(), inline_len);
+                result.set_len(inline_len);
+            }
+        }
         for mut vec in chunks.rest {
             result.append(&mut vec);
         }
-        result.append(&mut chunks.current);
+        let mut current = chunks.current;
+        result.append(&mut current);
         result
     }
+
+    /// Returns the number of values allocated so far in this arena.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use typed_arena::Arena;
+    ///
+    /// let arena = Arena::new();
+    ///
+    /// arena.alloc("a");
+    /// arena.alloc("b");
+    /// arena.alloc("c");
+    ///
+    /// assert_eq!(arena.len(), 3);
+    /// ```
+    pub fn len(&self) -> usize {
+        let chunks = self.chunks.borrow();
+        if chunks.current.capacity() == 0 {
+            return self.inline_used.get();
+        }
+        let current_len = if mem::size_of::<T>() != 0 {
+            (self.next.get() as usize - chunks.current.as_ptr() as usize) / mem::size_of::<T>()
+        } else {
+            chunks.current.len()
+        };
+        let heap_len = chunks.rest.iter().fold(current_len, |a, v| a + v.len());
+        self.inline_used.get() + heap_len
+    }
+
+    /// Returns `true` if this arena has no values allocated in it.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use typed_arena::Arena;
+    ///
+    /// let arena = Arena::new();
+    /// assert!(arena.is_empty());
+    ///
+    /// arena.alloc("a");
+    /// assert!(!arena.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops all values currently allocated in this arena, resetting it to
+    /// an empty state.
+    ///
+    /// The largest chunk allocated so far is kept around (and its memory
+    /// reused) rather than freed, so that allocation-heavy workloads that
+    /// call `clear` between passes — allocating a fresh AST per
+    /// compilation unit, say — don't pay for a new chunk on every pass. If
+    /// the arena never grew past its inline storage, that storage is simply
+    /// reused directly.
+    ///
+    /// Because this takes `&mut self`, it's sound even though the arena's
+    /// safety story otherwise relies on handing out long-lived references:
+    /// a `&mut Arena` guarantees there are no outstanding references to
+    /// anything the arena has allocated.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use typed_arena::Arena;
+    ///
+    /// let mut arena = Arena::new();
+    ///
+    /// arena.alloc("a");
+    /// arena.alloc("b");
+    /// assert_eq!(arena.len(), 2);
+    ///
+    /// arena.clear();
+    /// assert_eq!(arena.len(), 0);
+    ///
+    /// arena.alloc("c");
+    /// assert_eq!(arena.len(), 1);
+    /// ```
+    pub fn clear(&mut self) {
+        let chunks = self.chunks.get_mut();
+
+        if chunks.current.capacity() == 0 {
+            // Still inline: drop whatever's in `inline` and reset the count
+            // to zero, so the same inline storage gets reused by the next
+            // pass.
+            let len = self.inline_used.get();
+            if len != 0 {
+                let ptr = self.inline_ptr();
+                unsafe { ptr::drop_in_place(slice::from_raw_parts_mut(ptr, len)) };
+            }
+            self.inline_used.set(0);
+            return;
+        }
+
+        // `inline` is a permanent first chunk once the arena has spilled
+        // (see `spill_inline`), so it needs to drop its own values here
+        // too; it's never reused afterwards (`end` stays non-null forever),
+        // so its count is reset purely so `len`/`iter`/`Drop` see it as
+        // empty.
+        let inline_len = self.inline_used.get();
+        if inline_len != 0 {
+            let ptr = self.inline.get_mut().as_mut_ptr() as *mut T;
+            unsafe { ptr::drop_in_place(slice::from_raw_parts_mut(ptr, inline_len)) };
+            self.inline_used.set(0);
+        }
+
+        // Bring `current`'s length back in sync so that dropping it (or
+        // clearing it below) runs the right destructors.
+        if mem::size_of::<T>() != 0 {
+            let len =
+                (self.next.get() as usize - chunks.current.as_ptr() as usize) / mem::size_of::<T>();
+            unsafe { chunks.current.set_len(len) };
+        }
+
+        // Keep whichever chunk has the largest capacity and drop the rest
+        // (which also drops any values still stored in them).
+        let mut kept = mem::take(&mut chunks.current);
+        for chunk in chunks.rest.drain(..) {
+            if chunk.capacity() > kept.capacity() {
+                kept = chunk;
+            }
+        }
+        kept.clear();
+        chunks.current = kept;
+
+        let ptr = chunks.current.as_mut_ptr();
+        self.next.set(ptr);
+        // Safety: `clear` doesn't change `current`'s capacity, so this
+        // remains one past the last usable slot.
+        self.end.set(unsafe { ptr.add(chunks.current.capacity()) });
+    }
+}
+
+#[cfg(not(feature = "may_dangle"))]
+impl<T> Drop for Arena<T> {
+    fn drop(&mut self) {
+        let mut chunks = self.chunks.borrow_mut();
+        if chunks.current.capacity() == 0 {
+            // Still inline: `inline`'s elements aren't owned by a `Vec`
+            // that would otherwise drop them for us.
+            let len = self.inline_used.get();
+            if len != 0 {
+                let ptr = self.inline_ptr();
+                unsafe { ptr::drop_in_place(slice::from_raw_parts_mut(ptr, len)) };
+            }
+            return;
+        }
+        // `inline` is a permanent first chunk once the arena has spilled
+        // (see `spill_inline`): it isn't owned by a `Vec` that would drop
+        // it for us, so its values need dropping here too.
+        let len = self.inline_used.get();
+        if len != 0 {
+            let ptr = self.inline_ptr();
+            unsafe { ptr::drop_in_place(slice::from_raw_parts_mut(ptr, len)) };
+        }
+        // The fast path in `alloc` can leave `current.len()` lagging behind
+        // how many values were actually written into it; bring it back in
+        // sync so the `Vec`'s own `Drop` impl runs the right destructors.
+        self.sync_current_len(&mut chunks);
+    }
+}
+
+// Without `#[may_dangle]`, the mere presence of this `Drop` impl forces `T`
+// (and any lifetimes it borrows) to strictly outlive the `Arena`, which
+// rules out the cyclic, self-referential `T: Drop` structures described in
+// the "Safe Cycles" section of the crate docs. `#[may_dangle]` tells dropck
+// that `drop` below never lets safe code observe a `T` through a dangling
+// reference, which is true: the only thing it does with an allocated `T`
+// is drop it (the `inline` values directly, the rest through `chunks`'s
+// `Vec`s); otherwise it only touches the arena's own bookkeeping.
+#[cfg(feature = "may_dangle")]
+unsafe impl<#[may_dangle] T> Drop for Arena<T> {
+    fn drop(&mut self) {
+        let mut chunks = self.chunks.borrow_mut();
+        if chunks.current.capacity() == 0 {
+            // Still inline: `inline`'s elements aren't owned by a `Vec`
+            // that would otherwise drop them for us.
+            let len = self.inline_used.get();
+            if len != 0 {
+                let ptr = self.inline_ptr();
+                unsafe { ptr::drop_in_place(slice::from_raw_parts_mut(ptr, len)) };
+            }
+            return;
+        }
+        // `inline` is a permanent first chunk once the arena has spilled
+        // (see `spill_inline`): it isn't owned by a `Vec` that would drop
+        // it for us, so its values need dropping here too.
+        let len = self.inline_used.get();
+        if len != 0 {
+            let ptr = self.inline_ptr();
+            unsafe { ptr::drop_in_place(slice::from_raw_parts_mut(ptr, len)) };
+        }
+        // The fast path in `alloc` can leave `current.len()` lagging behind
+        // how many values were actually written into it; bring it back in
+        // sync so the `Vec`'s own `Drop` impl runs the right destructors.
+        self.sync_current_len(&mut chunks);
+    }
 }
 
 impl<T> ChunkList<T> {
@@ -397,6 +828,45 @@ impl<T> IterableArena<T> {
     pub fn iter(&self) -> impl Iterator<Item = &T> {
         Iter {
             done: false,
+            inline_i: 0,
+            chunk: 0,
+            i: 0,
+            arena: &self.arena,
+        }
+    }
+
+    /// Mutably iterate through the items allocated in this arena.
+    ///
+    /// Items in the iterator appear in the order that they were allocated in.
+    ///
+    /// Unlike `iter`, this takes `&mut self`, so the returned iterator can
+    /// hand out unique `&mut T` references: while it is alive nothing else
+    /// can allocate into or read from the arena. This is useful for a
+    /// build-then-fixup pass, e.g. resolving forward references in a graph
+    /// of nodes after they have all been allocated.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use typed_arena::IterableArena;
+    ///
+    /// let mut arena = IterableArena::new();
+    ///
+    /// arena.alloc(1);
+    /// arena.alloc(2);
+    /// arena.alloc(3);
+    ///
+    /// for value in arena.iter_mut() {
+    ///     *value *= 10;
+    /// }
+    ///
+    /// assert_eq!(arena.into_vec(), vec![10, 20, 30]);
+    /// ```
+    #[inline]
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        IterMut {
+            done: false,
+            inline_i: 0,
             chunk: 0,
             i: 0,
             arena: &self.arena,
@@ -445,8 +915,17 @@ impl<T> IterableArena<T> {
     }
 }
 
+impl<T> Default for IterableArena<T> {
+    fn default() -> Self {
+        IterableArena::new()
+    }
+}
+
 struct Iter<'a, T: 'a> {
     done: bool,
+    // Index into the frozen `inline` segment; exhausted before `chunk`/`i`
+    // (which walk `chunks`) are ever consulted.
+    inline_i: usize,
     chunk: usize,
     i: usize,
     arena: &'a Arena<T>,
@@ -459,9 +938,14 @@ impl<'a, T: 'a> Iterator for Iter<'a, T> {
     // 1) `!Sync`, in other words every operation on the arena
     //    happens atomically.
     // 2) Chunks in `rest` are frozen. They will never be pushed to again.
-    // 3) The only current chunk mutation is `Vec::push()`.
+    // 3) The only current chunk mutation is `Arena::alloc`'s bump pointer,
+    //    read here straight off `self.arena.next` rather than off
+    //    `chunks.current.len()`, which is allowed to lag behind it.
     // 4) There are no unique references (`mut`) to the items in the arena. This
     //    is only true for `IterableArena`.
+    // 5) `inline` is a permanent first chunk (see `Arena::spill_inline`):
+    //    it is never moved, so it's always safe to read `inline_i` of its
+    //    `inline_used` values before moving on to `chunks`.
     //
     // If while we are iterating the current chunk the arena allocates new
     // chunks then the next iteration will continue in the same chunk. Since
@@ -472,24 +956,530 @@ impl<'a, T: 'a> Iterator for Iter<'a, T> {
             return None;
         }
         let chunks = self.arena.chunks.borrow();
-        if self.chunk == chunks.rest.len() {
-            let chunk = &chunks.current;
-            if self.i == chunk.len() {
+        if chunks.current.capacity() == 0 {
+            // Still inline, and nothing has spilled onto the heap yet.
+            let len = self.arena.inline_used.get();
+            if self.inline_i == len {
                 self.done = true;
                 return None;
             }
+            let i = self.inline_i;
+            self.inline_i += 1;
+            let start = self.arena.inline_ptr();
+            return Some(unsafe { mem::transmute::<&T, &T>(&*start.add(i)) });
+        }
+        // Spilled: `inline_used` is now frozen, so drain it first — those
+        // values were allocated before anything in `chunks`.
+        if self.inline_i < self.arena.inline_used.get() {
+            let i = self.inline_i;
+            self.inline_i += 1;
+            let start = self.arena.inline_ptr();
+            return Some(unsafe { mem::transmute::<&T, &T>(&*start.add(i)) });
+        }
+        loop {
+            if self.chunk == chunks.rest.len() {
+                let start = chunks.current.as_ptr();
+                let len = if mem::size_of::<T>() != 0 {
+                    (self.arena.next.get() as usize - start as usize) / mem::size_of::<T>()
+                } else {
+                    chunks.current.len()
+                };
+                if self.i == len {
+                    self.done = true;
+                    return None;
+                }
+                let i = self.i;
+                self.i += 1;
+                return Some(unsafe { mem::transmute::<&T, &T>(&*start.add(i)) });
+            }
+            if self.i == chunks.rest[self.chunk].len() {
+                // This chunk is exhausted; move on to the next one (which
+                // may itself be `chunks.rest.len()`, i.e. `current`).
+                self.chunk += 1;
+                self.i = 0;
+                continue;
+            }
+            let chunk = &chunks.rest[self.chunk];
             let i = self.i;
             self.i += 1;
-            Some(unsafe { mem::transmute(&chunk[i]) })
-        } else {
-            let chunk = &chunks.rest[self.chunk];
-            if self.i == chunk.len() {
+            return Some(unsafe { mem::transmute::<&T, &T>(&chunk[i]) });
+        }
+    }
+}
+
+struct IterMut<'a, T: 'a> {
+    done: bool,
+    // Index into the frozen `inline` segment; exhausted before `chunk`/`i`
+    // (which walk `chunks`) are ever consulted.
+    inline_i: usize,
+    chunk: usize,
+    i: usize,
+    arena: &'a Arena<T>,
+}
+
+impl<'a, T: 'a> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    // Same reasoning as `Iter`, except assumption (4) no longer applies:
+    // `IterableArena::iter_mut` takes `&mut self`, so there are no other
+    // references — mutable or shared — to the arena's contents while this
+    // iterator is alive, which is what makes handing out `&mut T` sound.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let chunks = self.arena.chunks.borrow();
+        if chunks.current.capacity() == 0 {
+            // Still inline, and nothing has spilled onto the heap yet.
+            let len = self.arena.inline_used.get();
+            if self.inline_i == len {
+                self.done = true;
+                return None;
+            }
+            let i = self.inline_i;
+            self.inline_i += 1;
+            let start = self.arena.inline_ptr();
+            return Some(unsafe { mem::transmute::<&mut T, &mut T>(&mut *start.add(i)) });
+        }
+        // Spilled: `inline_used` is now frozen, so drain it first — those
+        // values were allocated before anything in `chunks`.
+        if self.inline_i < self.arena.inline_used.get() {
+            let i = self.inline_i;
+            self.inline_i += 1;
+            let start = self.arena.inline_ptr();
+            return Some(unsafe { mem::transmute::<&mut T, &mut T>(&mut *start.add(i)) });
+        }
+        loop {
+            if self.chunk == chunks.rest.len() {
+                let start = chunks.current.as_ptr().cast_mut();
+                let len = if mem::size_of::<T>() != 0 {
+                    (self.arena.next.get() as usize - start as usize) / mem::size_of::<T>()
+                } else {
+                    chunks.current.len()
+                };
+                if self.i == len {
+                    self.done = true;
+                    return None;
+                }
+                let i = self.i;
+                self.i += 1;
+                return Some(unsafe { mem::transmute::<&mut T, &mut T>(&mut *start.add(i)) });
+            }
+            if self.i == chunks.rest[self.chunk].len() {
+                // This chunk is exhausted; move on to the next one (which
+                // may itself be `chunks.rest.len()`, i.e. `current`).
                 self.chunk += 1;
                 self.i = 0;
+                continue;
             }
             let i = self.i;
             self.i += 1;
-            Some(unsafe { mem::transmute(&chunk[i]) })
+            let ptr = chunks.rest[self.chunk].as_ptr().cast_mut();
+            return Some(unsafe { mem::transmute::<&mut T, &mut T>(&mut *ptr.add(i)) });
         }
     }
 }
+
+/// An arena that can hold values of *any* type, as long as those types do
+/// not need to be dropped.
+///
+/// Unlike `Arena<T>`, which only ever holds values of a single type `T`,
+/// `DroplessArena` allocates raw, byte-aligned storage and can therefore
+/// intern values of many different types in the same arena. This is useful
+/// for things like interning strings alongside differently-shaped AST nodes.
+///
+/// Because the arena never runs destructors for the values it holds, `alloc`
+/// will debug-assert that `T` does not need to be dropped. Prefer `Arena<T>`
+/// whenever all of your values share a single type and need a destructor.
+///
+/// ## Example
+///
+/// ```
+/// use typed_arena::DroplessArena;
+///
+/// let arena = DroplessArena::new();
+///
+/// let number = arena.alloc(42u32);
+/// assert_eq!(*number, 42);
+///
+/// let greeting = arena.alloc_str("hello");
+/// assert_eq!(greeting, "hello");
+/// ```
+pub struct DroplessArena {
+    chunks: RefCell<DroplessChunkList>,
+}
+
+struct DroplessChunkList {
+    // Points at the next unused byte in the current chunk.
+    start: *mut u8,
+    // Points just past the end of the current chunk.
+    end: *mut u8,
+    // All chunks allocated so far, with the current chunk last. Older
+    // chunks are kept around purely so their memory stays alive; nothing
+    // allocates into them again.
+    chunks: Vec<Vec<u8>>,
+}
+
+impl DroplessArena {
+    /// Construct a new `DroplessArena`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use typed_arena::DroplessArena;
+    ///
+    /// let arena = DroplessArena::new();
+    /// # arena.alloc(1);
+    /// ```
+    pub fn new() -> DroplessArena {
+        DroplessArena {
+            chunks: RefCell::new(DroplessChunkList {
+                start: ptr::null_mut(),
+                end: ptr::null_mut(),
+                chunks: Vec::new(),
+            }),
+        }
+    }
+
+    /// Allocates a value in the arena, and returns a mutable reference to
+    /// that value.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use typed_arena::DroplessArena;
+    ///
+    /// let arena = DroplessArena::new();
+    /// let x = arena.alloc(42);
+    /// assert_eq!(*x, 42);
+    /// ```
+    #[inline]
+    #[allow(clippy::mut_from_ref)] // each call carves out a disjoint region, like `Arena::alloc`
+    pub fn alloc<T>(&self, value: T) -> &mut T {
+        debug_assert!(
+            !mem::needs_drop::<T>(),
+            "DroplessArena cannot hold a type that needs to be dropped"
+        );
+        let ptr = self.alloc_raw(Layout::new::<T>()).cast::<T>();
+        unsafe {
+            ptr::write(ptr, value);
+            &mut *ptr
+        }
+    }
+
+    /// Allocates a copy of the given string slice in the arena, and returns
+    /// a reference to it.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use typed_arena::DroplessArena;
+    ///
+    /// let arena = DroplessArena::new();
+    /// let s = arena.alloc_str("hello world");
+    /// assert_eq!(s, "hello world");
+    /// ```
+    #[inline]
+    pub fn alloc_str(&self, s: &str) -> &str {
+        let copy = self.alloc_slice_copy(s.as_bytes());
+        unsafe { str::from_utf8_unchecked(copy) }
+    }
+
+    /// Allocates a copy of the given slice in the arena, and returns a
+    /// mutable reference to it.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use typed_arena::DroplessArena;
+    ///
+    /// let arena = DroplessArena::new();
+    /// let abc = arena.alloc_slice_copy(&[1, 2, 3]);
+    /// assert_eq!(abc, [1, 2, 3]);
+    /// ```
+    #[allow(clippy::mut_from_ref)] // each call carves out a disjoint region, like `Arena::alloc`
+    pub fn alloc_slice_copy<T: Copy>(&self, slice: &[T]) -> &mut [T] {
+        if slice.is_empty() {
+            return &mut [];
+        }
+
+        let layout = Layout::for_value(slice);
+        let ptr = self.alloc_raw(layout).cast::<T>();
+        unsafe {
+            ptr::copy_nonoverlapping(slice.as_ptr(), ptr, slice.len());
+            slice::from_raw_parts_mut(ptr, slice.len())
+        }
+    }
+
+    /// Carves `layout.size()` bytes, aligned to `layout.align()`, out of the
+    /// current chunk, growing into a fresh chunk first if there isn't enough
+    /// room left.
+    fn alloc_raw(&self, layout: Layout) -> *mut u8 {
+        if layout.size() == 0 {
+            // There are no bytes to carve out, so don't touch the chunks at
+            // all. A pointer merely aligned to `layout.align()` is a valid,
+            // well-aligned (if not dereferenceable) place to "store" a
+            // zero-sized value, matching rustc_arena's treatment of ZSTs.
+            return layout.align() as *mut u8;
+        }
+
+        let mut chunks = self.chunks.borrow_mut();
+
+        let align = layout.align();
+        let aligned_start = (chunks.start as usize + align - 1) & !(align - 1);
+        match aligned_start.checked_add(layout.size()) {
+            Some(new_start) if new_start <= chunks.end as usize => {
+                chunks.start = new_start as *mut u8;
+                aligned_start as *mut u8
+            }
+            _ => {
+                chunks.grow(layout.size() + align - 1);
+
+                let aligned_start = (chunks.start as usize + align - 1) & !(align - 1);
+                let new_start = aligned_start + layout.size();
+                chunks.start = new_start as *mut u8;
+                aligned_start as *mut u8
+            }
+        }
+    }
+}
+
+impl Default for DroplessArena {
+    fn default() -> Self {
+        DroplessArena::new()
+    }
+}
+
+impl DroplessChunkList {
+    #[inline(never)]
+    #[cold]
+    fn grow(&mut self, additional: usize) {
+        let double_cap = self
+            .chunks
+            .last()
+            .map_or(INITIAL_SIZE, |chunk| {
+                chunk.capacity().checked_mul(2).expect("capacity overflow")
+            });
+        let required_cap = additional
+            .checked_next_power_of_two()
+            .expect("capacity overflow");
+        let new_capacity = cmp::max(double_cap, required_cap);
+
+        let mut chunk: Vec<u8> = Vec::with_capacity(new_capacity);
+        let start = chunk.as_mut_ptr();
+        // Safety: `chunk` has capacity `new_capacity`, so this is one byte
+        // past the end of its allocation, which is a valid pointer to form.
+        let end = unsafe { start.add(new_capacity) };
+        self.start = start;
+        self.end = end;
+        self.chunks.push(chunk);
+    }
+}
+
+/// A thread-safe arena of objects of type `T`.
+///
+/// `SyncArena` is `Arena`'s `Sync` counterpart: instead of a `RefCell`, its
+/// chunk list is guarded by a `Mutex`, so multiple threads can allocate into
+/// the same arena concurrently. Because allocation now requires taking a
+/// lock, `alloc` hands back a shared reference (`&T`) rather than a unique
+/// one — concurrent writers rule out returning `&mut T`, the same reasoning
+/// `IterableArena` relies on for its single-threaded, shared-iteration case.
+///
+/// Requires the `std` feature, since it is built on `std::sync::Mutex`.
+///
+/// ## Panics
+///
+/// Every method that touches the chunk list takes the internal `Mutex` with
+/// `.lock().unwrap()`. If one thread panics while holding that lock (e.g.
+/// inside the iterator passed to `alloc_extend`, which is driven while the
+/// lock is held), the mutex is
+/// poisoned and every later call from any thread will itself panic. This
+/// mirrors `std`'s own stance on poisoned mutexes: treat a panic while
+/// allocating into a `SyncArena` as corrupting that arena for good, rather
+/// than silently continuing past a broken invariant.
+///
+/// ## Example
+///
+/// ```
+/// use typed_arena::SyncArena;
+///
+/// let arena = SyncArena::new();
+/// let a = arena.alloc(1);
+/// let b = arena.alloc(2);
+/// assert_eq!(*a + *b, 3);
+/// ```
+#[cfg(feature = "std")]
+pub struct SyncArena<T> {
+    chunks: Mutex<SyncChunkList<T>>,
+}
+
+#[cfg(feature = "std")]
+struct SyncChunkList<T> {
+    current: Vec<T>,
+    rest: Vec<Vec<T>>,
+}
+
+#[cfg(feature = "std")]
+impl<T> SyncArena<T> {
+    /// Construct a new `SyncArena`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use typed_arena::SyncArena;
+    ///
+    /// let arena = SyncArena::new();
+    /// # arena.alloc(1);
+    /// ```
+    pub fn new() -> SyncArena<T> {
+        let size = cmp::max(1, mem::size_of::<T>());
+        SyncArena::with_capacity(INITIAL_SIZE / size)
+    }
+
+    /// Construct a new `SyncArena` with capacity for `n` values pre-allocated.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use typed_arena::SyncArena;
+    ///
+    /// let arena = SyncArena::with_capacity(1337);
+    /// # arena.alloc(1);
+    /// ```
+    pub fn with_capacity(n: usize) -> SyncArena<T> {
+        let n = cmp::max(MIN_CAPACITY, n);
+        SyncArena {
+            chunks: Mutex::new(SyncChunkList {
+                current: Vec::with_capacity(n),
+                rest: Vec::new(),
+            }),
+        }
+    }
+
+    /// Allocates a value in the arena, and returns a shared reference to
+    /// that value.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use typed_arena::SyncArena;
+    ///
+    /// let arena = SyncArena::new();
+    /// let x = arena.alloc(42);
+    /// assert_eq!(*x, 42);
+    /// ```
+    #[inline]
+    pub fn alloc(&self, value: T) -> &T {
+        self.alloc_fast_path(value)
+            .unwrap_or_else(|value| self.alloc_slow_path(value))
+    }
+
+    #[inline]
+    fn alloc_fast_path(&self, value: T) -> Result<&T, T> {
+        let mut chunks = self.chunks.lock().unwrap();
+        if chunks.current.len() < chunks.current.capacity() {
+            chunks.current.push(value);
+            Ok(unsafe { mem::transmute::<&T, &T>(chunks.current.last().unwrap()) })
+        } else {
+            Err(value)
+        }
+    }
+
+    fn alloc_slow_path(&self, value: T) -> &T {
+        &self.alloc_extend(iter::once(value))[0]
+    }
+
+    /// Uses the contents of an iterator to allocate values in the arena.
+    /// Returns a shared slice that contains these values.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use typed_arena::SyncArena;
+    ///
+    /// let arena = SyncArena::new();
+    /// let abc = arena.alloc_extend("abcdefg".chars().take(3));
+    /// assert_eq!(abc, ['a', 'b', 'c']);
+    /// ```
+    pub fn alloc_extend<I>(&self, iterable: I) -> &[T]
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iterable.into_iter();
+
+        let mut chunks = self.chunks.lock().unwrap();
+
+        let iter_min_len = iter.size_hint().0;
+        let mut next_item_index;
+        if chunks.current.len() + iter_min_len > chunks.current.capacity() {
+            chunks.reserve(iter_min_len);
+            chunks.current.extend(iter);
+            next_item_index = 0;
+        } else {
+            next_item_index = chunks.current.len();
+            let mut i = 0;
+            while let Some(elem) = iter.next() {
+                if chunks.current.len() == chunks.current.capacity() {
+                    // The iterator was larger than we could fit into the current chunk.
+                    let chunks = &mut *chunks;
+                    // Create a new chunk into which we can freely push the entire iterator into
+                    chunks.reserve(i + 1);
+                    let previous_chunk = chunks.rest.last_mut().unwrap();
+                    let previous_chunk_len = previous_chunk.len();
+                    // Move any elements we put into the previous chunk into this new chunk
+                    chunks
+                        .current
+                        .extend(previous_chunk.drain(previous_chunk_len - i..));
+                    chunks.current.push(elem);
+                    // And the remaining elements in the iterator
+                    chunks.current.extend(iter);
+                    next_item_index = 0;
+                    break;
+                } else {
+                    chunks.current.push(elem);
+                }
+                i += 1;
+            }
+        }
+        let new_slice_ref = {
+            let new_slice_ref = &chunks.current[next_item_index..];
+
+            // Extend the lifetime from that of `chunks` to that of `self`.
+            // This is OK for the same reason it's OK in `Arena::alloc_extend`:
+            // we're careful to never move items by never pushing to inner
+            // `Vec`s beyond their initial capacity. The returned reference is
+            // shared (`&`), matching the fact that `SyncArena` — like
+            // `IterableArena` — never hands out a unique reference to an
+            // item once it's allocated.
+            unsafe { mem::transmute::<&[T], &[T]>(new_slice_ref) }
+        };
+
+        new_slice_ref
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Default for SyncArena<T> {
+    fn default() -> Self {
+        SyncArena::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> SyncChunkList<T> {
+    #[inline(never)]
+    #[cold]
+    fn reserve(&mut self, additional: usize) {
+        let double_cap = self
+            .current
+            .capacity()
+            .checked_mul(2)
+            .expect("capacity overflow");
+        let required_cap = additional
+            .checked_next_power_of_two()
+            .expect("capacity overflow");
+        let new_capacity = cmp::max(double_cap, required_cap);
+        let chunk = mem::replace(&mut self.current, Vec::with_capacity(new_capacity));
+        self.rest.push(chunk);
+    }
+}
@@ -0,0 +1,243 @@
+use super::*;
+
+// chunk0-1: `DroplessArena` is byte-oriented, so it needs to hold
+// differently-sized and -aligned types side by side, including
+// zero-sized ones, without misaligning any of them.
+#[test]
+fn dropless_arena_mixed_types_stay_aligned() {
+    let arena = DroplessArena::new();
+    let a: &u8 = arena.alloc(1u8);
+    let b: &u64 = arena.alloc(2u64);
+    let c: &() = arena.alloc(());
+    let d: &u32 = arena.alloc(3u32);
+
+    assert_eq!(*a, 1);
+    assert_eq!(*b, 2);
+    assert_eq!(*c, ());
+    assert_eq!(*d, 3);
+    assert_eq!(b as *const u64 as usize % mem::align_of::<u64>(), 0);
+    assert_eq!(d as *const u32 as usize % mem::align_of::<u32>(), 0);
+}
+
+#[test]
+fn dropless_arena_zero_sized_allocations_are_valid() {
+    let arena = DroplessArena::new();
+    let a = arena.alloc(());
+    let b = arena.alloc(());
+    // Both just need to be valid, aligned pointers; a ZST arena doesn't
+    // need to (and can't) give them distinct addresses.
+    assert_eq!(*a, ());
+    assert_eq!(*b, ());
+}
+
+#[test]
+fn dropless_arena_alloc_str_and_slice_copy() {
+    let arena = DroplessArena::new();
+    let s = arena.alloc_str("hello world");
+    assert_eq!(s, "hello world");
+
+    let empty = arena.alloc_slice_copy::<u32>(&[]);
+    assert!(empty.is_empty());
+
+    let abc = arena.alloc_slice_copy(&[1, 2, 3]);
+    assert_eq!(abc, [1, 2, 3]);
+}
+
+// chunk0-2: the pointer-bump fast path used once the arena is backed by a
+// heap chunk must keep previously returned references stable as the arena
+// grows into new chunks, and `alloc_extend` must hand back its values in
+// allocation order.
+#[test]
+fn reference_survives_heap_chunk_growth() {
+    let arena = Arena::with_capacity(INLINE_CAPACITY + 1);
+    let first = arena.alloc(1);
+    for i in 0..1000 {
+        arena.alloc(i);
+    }
+    assert_eq!(*first, 1);
+}
+
+#[test]
+fn alloc_extend_preserves_order() {
+    let arena = Arena::new();
+    let slice = arena.alloc_extend(0..50);
+    assert_eq!(slice, (0..50).collect::<Vec<_>>().as_slice());
+}
+
+// chunk0-3: with the `may_dangle` feature, values allocated in the same
+// arena are allowed to reference each other through a custom `Drop`, since
+// dropck no longer requires those references to outlive the `Drop` call.
+#[cfg(feature = "may_dangle")]
+#[test]
+fn may_dangle_allows_self_referential_cycle() {
+    use core::cell::Cell as StdCell;
+
+    struct Cyclic<'a> {
+        other: StdCell<Option<&'a Cyclic<'a>>>,
+        dropped: &'a StdCell<usize>,
+    }
+
+    impl<'a> Drop for Cyclic<'a> {
+        fn drop(&mut self) {
+            self.other.set(None);
+            self.dropped.set(self.dropped.get() + 1);
+        }
+    }
+
+    let dropped = StdCell::new(0);
+    {
+        let arena = Arena::new();
+        let a = arena.alloc(Cyclic {
+            other: StdCell::new(None),
+            dropped: &dropped,
+        });
+        let b = arena.alloc(Cyclic {
+            other: StdCell::new(None),
+            dropped: &dropped,
+        });
+        a.other.set(Some(b));
+        b.other.set(Some(a));
+    }
+    assert_eq!(dropped.get(), 2);
+}
+
+// chunk0-4: several threads hammering `alloc`/`alloc_extend` on the same
+// `&SyncArena` concurrently must each get back a reference to a distinct,
+// correctly-written value, exercising the mutex-guarded regrow path.
+#[cfg(feature = "std")]
+#[test]
+fn sync_arena_concurrent_alloc_and_alloc_extend() {
+    use std::sync::Arc;
+    use std::thread;
+
+    let arena = Arc::new(SyncArena::new());
+    let mut handles = Vec::new();
+
+    for t in 0..8 {
+        let arena = Arc::clone(&arena);
+        handles.push(thread::spawn(move || {
+            let mut refs = Vec::new();
+            for i in 0..200 {
+                refs.push(arena.alloc(t * 1000 + i));
+            }
+            let extended = arena.alloc_extend((0..50).map(|i| t * 1000 + i));
+            for (i, v) in refs.iter().enumerate() {
+                assert_eq!(**v, t * 1000 + i as i32);
+            }
+            for (i, v) in extended.iter().enumerate() {
+                assert_eq!(*v, t * 1000 + i as i32);
+            }
+        }));
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+// chunk0-5: `clear` drops everything allocated so far and lets the arena
+// be reused from scratch, and `len`/`is_empty` must track that accurately.
+#[test]
+fn clear_resets_len_and_allows_reallocation() {
+    let mut arena = Arena::new();
+    assert!(arena.is_empty());
+    assert_eq!(arena.len(), 0);
+
+    for i in 0..20 {
+        arena.alloc(i);
+    }
+    assert_eq!(arena.len(), 20);
+    assert!(!arena.is_empty());
+
+    arena.clear();
+    assert!(arena.is_empty());
+    assert_eq!(arena.len(), 0);
+
+    for i in 0..5 {
+        arena.alloc(i);
+    }
+    assert_eq!(arena.len(), 5);
+    assert_eq!(arena.into_vec(), (0..5).collect::<Vec<_>>());
+}
+
+#[test]
+fn clear_drops_inline_values() {
+    use std::rc::Rc;
+
+    let mut arena = Arena::new();
+    let counter = Rc::new(());
+    for _ in 0..3 {
+        arena.alloc(Rc::clone(&counter));
+    }
+    assert_eq!(Rc::strong_count(&counter), 4);
+
+    arena.clear();
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+// chunk0-6: `inline` is a permanent first chunk, so references handed out
+// of it must stay valid even after later allocations force the arena to
+// spill onto the heap.
+#[test]
+fn reference_survives_spill_to_heap() {
+    let arena = Arena::new();
+    let first = arena.alloc(1);
+    assert_eq!(*first, 1);
+
+    // `INLINE_CAPACITY` is 8, so this forces at least one spill.
+    for i in 0..20 {
+        arena.alloc(100 + i);
+    }
+
+    *first = 99;
+    assert_eq!(arena.into_vec()[0], 99);
+}
+
+#[test]
+fn into_vec_keeps_allocation_order_across_spill() {
+    let arena = Arena::new();
+    let values: Vec<i32> = (0..20).collect();
+    for &v in &values {
+        arena.alloc(v);
+    }
+    assert_eq!(arena.into_vec(), values);
+}
+
+// chunk0-7: `IterableArena::iter_mut` hands out unique references to every
+// value allocated so far, which a caller can use to fix up values after
+// they've all been allocated; `iter` keeps working the same way it always
+// has, across inline and heap-backed storage alike.
+#[test]
+fn iterable_arena_iter_mut_fixes_up_all_values() {
+    let mut arena = IterableArena::new();
+    for i in 0..20 {
+        arena.alloc(i);
+    }
+
+    for value in arena.iter_mut() {
+        *value *= 10;
+    }
+
+    assert_eq!(
+        arena.into_vec(),
+        (0..20).map(|i| i * 10).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn iterable_arena_iter_sees_values_allocated_during_iteration() {
+    let arena = IterableArena::new();
+    arena.alloc(1);
+    arena.alloc(2);
+
+    let mut iter = arena.iter();
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next(), Some(&2));
+
+    arena.alloc(3);
+    assert_eq!(iter.next(), Some(&3));
+    assert_eq!(iter.next(), None);
+
+    arena.alloc(4);
+    assert_eq!(iter.next(), None);
+}